@@ -1,5 +1,5 @@
 use hemera::measure_time;
-use tokio::time::{Duration, sleep};
+use tokio::time::{sleep, Duration};
 
 #[measure_time]
 async fn fetch_data() -> String {
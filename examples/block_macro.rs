@@ -0,0 +1,28 @@
+use hemera::block::measure_time;
+
+fn run_query(fail: bool) -> Result<u32, String> {
+    if fail {
+        Err("query failed".to_string())
+    } else {
+        Ok(42)
+    }
+}
+
+fn load(fail: bool) -> Result<u32, String> {
+    // `?` propagates out of `load`, not out of the macro: the block is
+    // timed in place, not wrapped in a closure.
+    let rows = measure_time!(name = "db_query", { run_query(fail)? });
+    Ok(rows * 2)
+}
+
+#[tokio::main]
+async fn main() {
+    println!("ok: {:?}", load(false));
+    println!("err: {:?}", load(true));
+
+    let data = measure_time!(name = "fetch", {
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        "fetched"
+    });
+    println!("data: {data}");
+}
@@ -0,0 +1,31 @@
+use hemera::{measure_time, Sink};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+/// A sink that just counts how many samples it has seen, to show that
+/// installing a `Sink` really does take over from the default printing.
+struct CountingSink {
+    calls: AtomicU64,
+}
+
+impl Sink for CountingSink {
+    fn record(&self, name: &str, elapsed: Duration) {
+        let count = self.calls.fetch_add(1, Ordering::Relaxed) + 1;
+        println!("[sink] sample #{count} for `{name}`: {elapsed:.3?}");
+    }
+}
+
+#[measure_time]
+fn work(n: u32) -> u32 {
+    (0..n).sum()
+}
+
+fn main() {
+    hemera::set_sink(CountingSink {
+        calls: AtomicU64::new(0),
+    });
+
+    for i in 1..=3 {
+        work(i * 1000);
+    }
+}
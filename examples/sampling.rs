@@ -0,0 +1,17 @@
+use hemera::measure_time;
+
+#[measure_time(samples = 20)]
+fn noisy_work(n: u32) -> u32 {
+    // A few artificially slow calls mixed into a fast baseline, to show the
+    // median/MAD summary shrug off outliers that a single reading wouldn't.
+    if n.is_multiple_of(7) {
+        std::thread::sleep(std::time::Duration::from_millis(5));
+    }
+    (0..n).sum()
+}
+
+fn main() {
+    for i in 1..=100 {
+        noisy_work(i * 10);
+    }
+}
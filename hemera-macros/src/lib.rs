@@ -0,0 +1,391 @@
+//! Proc-macro implementation backing the `hemera` crate.
+//!
+//! This crate only exists because `proc-macro = true` crates may not export
+//! anything but macros. The public API (the macro itself, the runtime
+//! registry, the `Sink` trait, ...) lives in `hemera`, which depends on this
+//! crate and re-exports `measure_time`. Generated code therefore refers back
+//! into `hemera::__private::*`, which is always in scope for anyone who
+//! imported the macro.
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{
+    parse::{Parse, ParseStream},
+    parse_macro_input,
+    punctuated::Punctuated,
+    Expr, ItemFn, Lit, Meta, MetaNameValue, Token,
+};
+
+/// Configuration for the `measure_time` macro
+struct HemeraConfig {
+    name: Option<String>,
+    level: Option<String>,
+    threshold: Option<String>,
+    aggregate: bool,
+    samples: Option<usize>,
+}
+
+impl Parse for HemeraConfig {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut config = HemeraConfig {
+            name: None,
+            level: None,
+            threshold: None,
+            aggregate: false,
+            samples: None,
+        };
+
+        let metas = Punctuated::<Meta, Token![,]>::parse_terminated(input)?;
+
+        for meta in metas {
+            match meta {
+                Meta::NameValue(MetaNameValue { path, value, .. }) => {
+                    let ident = path
+                        .get_ident()
+                        .ok_or_else(|| syn::Error::new_spanned(&path, "Expected identifier"))?;
+
+                    match ident.to_string().as_str() {
+                        "name" => {
+                            if let Expr::Lit(expr_lit) = value {
+                                if let Lit::Str(lit_str) = &expr_lit.lit {
+                                    config.name = Some(lit_str.value());
+                                }
+                            }
+                        }
+                        "level" => {
+                            if let Expr::Lit(expr_lit) = value {
+                                if let Lit::Str(lit_str) = &expr_lit.lit {
+                                    let level = lit_str.value();
+                                    if !matches!(
+                                        level.as_str(),
+                                        "trace" | "debug" | "info" | "warn" | "error"
+                                    ) {
+                                        return Err(syn::Error::new_spanned(
+                                            lit_str,
+                                            "level must be one of \"trace\", \"debug\", \"info\", \"warn\", \"error\"",
+                                        ));
+                                    }
+                                    config.level = Some(level);
+                                }
+                            }
+                        }
+                        "threshold" => {
+                            if let Expr::Lit(expr_lit) = value {
+                                if let Lit::Str(lit_str) = &expr_lit.lit {
+                                    config.threshold = Some(lit_str.value());
+                                }
+                            }
+                        }
+                        "aggregate" => {
+                            if let Expr::Lit(expr_lit) = value {
+                                if let Lit::Bool(lit_bool) = &expr_lit.lit {
+                                    config.aggregate = lit_bool.value;
+                                } else {
+                                    return Err(syn::Error::new_spanned(
+                                        expr_lit,
+                                        "aggregate must be a bool literal",
+                                    ));
+                                }
+                            }
+                        }
+                        "samples" => {
+                            if let Expr::Lit(expr_lit) = value {
+                                if let Lit::Int(lit_int) = &expr_lit.lit {
+                                    let n: usize = lit_int.base10_parse()?;
+                                    if n == 0 {
+                                        return Err(syn::Error::new_spanned(
+                                            lit_int,
+                                            "samples must be greater than zero",
+                                        ));
+                                    }
+                                    config.samples = Some(n);
+                                } else {
+                                    return Err(syn::Error::new_spanned(
+                                        expr_lit,
+                                        "samples must be an integer literal",
+                                    ));
+                                }
+                            }
+                        }
+                        _ => {
+                            return Err(syn::Error::new_spanned(
+                                ident,
+                                format!("Unknown attribute: {}", ident),
+                            ));
+                        }
+                    }
+                }
+                Meta::Path(path) => {
+                    let ident = path
+                        .get_ident()
+                        .ok_or_else(|| syn::Error::new_spanned(&path, "Expected identifier"))?;
+                    if ident == "aggregate" {
+                        config.aggregate = true;
+                    } else {
+                        return Err(syn::Error::new_spanned(
+                            ident,
+                            format!("Unknown attribute: {}", ident),
+                        ));
+                    }
+                }
+                _ => {
+                    return Err(syn::Error::new_spanned(meta, "Expected name-value pair"));
+                }
+            }
+        }
+
+        Ok(config)
+    }
+}
+
+/// Parse threshold string like "10ms", "1s", "500us" into Duration expression
+fn parse_threshold(threshold_str: &str) -> syn::Result<proc_macro2::TokenStream> {
+    let threshold_str = threshold_str.trim();
+
+    let (value_str, unit) = if let Some(stripped) = threshold_str.strip_suffix("ms") {
+        (stripped, "millis")
+    } else if let Some(stripped) = threshold_str
+        .strip_suffix("us")
+        .or_else(|| threshold_str.strip_suffix("µs"))
+    {
+        (stripped, "micros")
+    } else if let Some(stripped) = threshold_str.strip_suffix("ns") {
+        (stripped, "nanos")
+    } else if let Some(stripped) = threshold_str.strip_suffix('s') {
+        (stripped, "secs")
+    } else {
+        return Err(syn::Error::new(
+            proc_macro2::Span::call_site(),
+            "Threshold must end with 'ms', 'us', 'ns', or 's'",
+        ));
+    };
+
+    let value: u64 = value_str.parse().map_err(|_| {
+        syn::Error::new(
+            proc_macro2::Span::call_site(),
+            format!("Invalid threshold value: {}", value_str),
+        )
+    })?;
+
+    let duration = match unit {
+        "secs" => quote! { std::time::Duration::from_secs(#value) },
+        "millis" => quote! { std::time::Duration::from_millis(#value) },
+        "micros" => quote! { std::time::Duration::from_micros(#value) },
+        "nanos" => quote! { std::time::Duration::from_nanos(#value) },
+        _ => unreachable!(),
+    };
+
+    Ok(duration)
+}
+
+/// Attribute macro for measuring function execution time
+///
+/// # Arguments
+///
+/// * `name` - Custom name for the function in logs (default: function name)
+/// * `level` - Log level: "trace", "debug", "info", "warn", or "error" (default: "info").
+///   Without the `tracing` feature, "info" prints via `println!` and everything else via
+///   `eprintln!`; with it, this selects the `tracing::Level` of the emitted event. Combined
+///   with `samples`, it instead picks stdout vs. stderr for that mode's periodic summary
+///   (even under the `tracing` feature, `samples` mode logs its summary this way
+///   rather than as a tracing event).
+/// * `threshold` - Minimum duration to log (e.g., "10ms", "1s") (default: always log).
+///   Mutually exclusive with `aggregate`, which must see every call to produce an
+///   unbiased histogram.
+/// * `aggregate` - When `true`, record every call into the global stats registry
+///   (see `hemera::report`) instead of printing each call individually. Requires
+///   the `stats` feature.
+/// * `samples` - Instead of logging every call, keep the last `N` durations in a
+///   ring buffer and, once full, log one line of robust summary statistics
+///   (median, MAD, min) per `N` calls. Mutually exclusive with `aggregate`;
+///   combines with `threshold` to only summarize once the median exceeds it.
+///
+/// # Examples
+///
+/// This macro is re-exported as `hemera::measure_time`; `hemera-macros` itself
+/// can't depend on `hemera` (that would be circular), so this example isn't
+/// run as a doctest here -- see it exercised for real in `hemera`'s own docs.
+///
+/// ```rust,ignore
+/// use hemera::measure_time;
+///
+/// #[measure_time]
+/// fn example() {
+///     // Function body
+/// }
+///
+/// #[measure_time(name = "MyFunction", level = "debug", threshold = "10ms")]
+/// fn example_with_options() {
+///     // Function body
+/// }
+///
+/// #[measure_time]
+/// async fn async_example() {
+///     // Async function body
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn measure_time(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(item as ItemFn);
+
+    let config = if attr.is_empty() {
+        HemeraConfig {
+            name: None,
+            level: None,
+            threshold: None,
+            aggregate: false,
+            samples: None,
+        }
+    } else {
+        match syn::parse::<HemeraConfig>(attr) {
+            Ok(config) => config,
+            Err(e) => return e.to_compile_error().into(),
+        }
+    };
+
+    if config.aggregate && config.samples.is_some() {
+        return syn::Error::new(
+            proc_macro2::Span::call_site(),
+            "`aggregate` and `samples` are mutually exclusive",
+        )
+        .to_compile_error()
+        .into();
+    }
+
+    if config.aggregate && config.threshold.is_some() {
+        return syn::Error::new(
+            proc_macro2::Span::call_site(),
+            "`aggregate` and `threshold` are mutually exclusive: aggregate mode records \
+             every call into the global stats registry, so a threshold would silently \
+             undercount `n` and skew percentiles toward the slow tail",
+        )
+        .to_compile_error()
+        .into();
+    }
+
+    let ItemFn {
+        attrs,
+        vis,
+        sig,
+        block,
+    } = input;
+
+    let fn_name = &sig.ident;
+    let display_name = config.name.unwrap_or_else(|| fn_name.to_string());
+
+    let level_str = config.level.unwrap_or_else(|| "info".to_string());
+    let use_stderr = matches!(level_str.as_str(), "trace" | "debug" | "warn" | "error");
+    let tracing_level = match level_str.as_str() {
+        "trace" => quote! { hemera::__private::tracing::Level::TRACE },
+        "debug" => quote! { hemera::__private::tracing::Level::DEBUG },
+        "info" => quote! { hemera::__private::tracing::Level::INFO },
+        "warn" => quote! { hemera::__private::tracing::Level::WARN },
+        "error" => quote! { hemera::__private::tracing::Level::ERROR },
+        _ => unreachable!("validated in HemeraConfig::parse"),
+    };
+
+    let threshold_duration = match config.threshold {
+        Some(threshold_str) => match parse_threshold(&threshold_str) {
+            Ok(duration) => Some(duration),
+            Err(e) => return e.to_compile_error().into(),
+        },
+        None => None,
+    };
+
+    let threshold_check = match &threshold_duration {
+        Some(duration) => quote! { if __hemera_elapsed >= #duration },
+        None => quote! { if true },
+    };
+
+    let threshold_opt = match &threshold_duration {
+        Some(duration) => quote! { Some(#duration) },
+        None => quote! { None },
+    };
+
+    let is_async = sig.asyncness.is_some();
+
+    // Under the `tracing` feature the elapsed time is recorded as a structured
+    // event on the span opened by `tracing_wrapper` below, instead of being
+    // printed or sent to a `Sink`.
+    let non_aggregate_stmt = if cfg!(feature = "tracing") {
+        quote! {
+            hemera::__private::tracing::event!(
+                #tracing_level,
+                function = #display_name,
+                elapsed_ms = __hemera_elapsed.as_secs_f64() * 1000.0,
+                "function completed"
+            );
+        }
+    } else {
+        quote! {
+            hemera::__private::dispatch(#display_name, __hemera_elapsed, #use_stderr);
+        }
+    };
+
+    let record_stmt = if config.aggregate {
+        if cfg!(feature = "stats") {
+            quote! {
+                hemera::__private::record_aggregate(#display_name, __hemera_elapsed);
+            }
+        } else {
+            quote! {
+                compile_error!(
+                    "`aggregate = true` requires the `stats` feature of the `hemera` crate"
+                );
+            }
+        }
+    } else if let Some(samples) = config.samples {
+        quote! {
+            hemera::__private::record_sample(#display_name, #samples, __hemera_elapsed, #threshold_opt, #use_stderr);
+        }
+    } else {
+        quote! {
+            #threshold_check {
+                #non_aggregate_stmt
+            }
+        }
+    };
+
+    let timing_code = if is_async {
+        quote! {
+            let __hemera_start = std::time::Instant::now();
+            let __hemera_result = async move { #block }.await;
+            let __hemera_elapsed = __hemera_start.elapsed();
+            #record_stmt
+            __hemera_result
+        }
+    } else {
+        quote! {
+            let __hemera_start = std::time::Instant::now();
+            let __hemera_result = (|| #block)();
+            let __hemera_elapsed = __hemera_start.elapsed();
+            #record_stmt
+            __hemera_result
+        }
+    };
+
+    let tracing_wrapper = if cfg!(feature = "tracing") {
+        quote! {
+            let __hemera_span = hemera::__private::tracing::span!(#tracing_level, "hemera", function = #display_name);
+            let __hemera_enter = __hemera_span.enter();
+        }
+    } else {
+        quote! {}
+    };
+
+    let new_block = quote! {
+        {
+            #tracing_wrapper
+            #timing_code
+        }
+    };
+
+    let output = quote! {
+        #(#attrs)*
+        #vis #sig {
+            #new_block
+        }
+    };
+
+    output.into()
+}
@@ -0,0 +1,177 @@
+//! Block-level companion to `#[measure_time]`.
+//!
+//! The attribute macro can only wrap a whole `fn`. `measure_time!` times an
+//! arbitrary block expression in place and hands back its value, so a
+//! sub-region, a loop body, or a single `.await` point can be instrumented
+//! without pulling it out into its own function:
+//!
+//! ```rust
+//! use hemera::block::measure_time;
+//!
+//! let rows = measure_time!(name = "db_query", { 1 + 1 });
+//! ```
+//!
+//! It lives at `hemera::block::measure_time` rather than the crate root:
+//! Rust's macro namespace won't let a bang-style macro share a name with
+//! the `#[measure_time]` attribute.
+//!
+//! The block is timed in place rather than wrapped in a closure, so a `?`
+//! or `.await` inside it behaves exactly as if the macro weren't there —
+//! it propagates out of (or suspends) the enclosing function, not some
+//! inner closure.
+
+use std::time::Duration;
+
+/// Parses the digits in `bytes[start..end]` as a `u64`, at compile time.
+///
+/// Hand-rolled rather than `str::parse`, which isn't `const fn`: a panic
+/// here during `const` evaluation is what turns a malformed threshold into
+/// a build failure instead of a runtime one. Panicking in a `const fn` can't
+/// interpolate the offending value into the message (formatting isn't
+/// `const`), so the message stays static.
+const fn __parse_threshold_digits(bytes: &[u8], start: usize, end: usize) -> u64 {
+    if start >= end {
+        panic!("hemera: invalid threshold value");
+    }
+
+    let mut value: u64 = 0;
+    let mut i = start;
+    while i < end {
+        let byte = bytes[i];
+        if !byte.is_ascii_digit() {
+            panic!("hemera: invalid threshold value");
+        }
+        value = value * 10 + (byte - b'0') as u64;
+        i += 1;
+    }
+    value
+}
+
+/// Parses a threshold string like "10ms", "1s", "500us" into a `Duration`,
+/// at compile time.
+///
+/// Mirrors the parser used by `#[measure_time(threshold = ...)]`, but runs
+/// during `const` evaluation here rather than at macro-expansion time, since
+/// `measure_time!` is a declarative macro rather than a proc-macro. `const fn` (rather than a plain runtime `fn`,
+/// as this used to be) is what makes that mirroring exact: bound through a
+/// `const` below, a malformed literal panics during evaluation of that
+/// `const`, which rustc reports as a compile error rather than a panic the
+/// first time the call site runs.
+#[doc(hidden)]
+pub const fn __parse_threshold(spec: &str) -> Duration {
+    let bytes = spec.as_bytes();
+
+    let mut start = 0;
+    let mut end = bytes.len();
+    while start < end && bytes[start].is_ascii_whitespace() {
+        start += 1;
+    }
+    while end > start && bytes[end - 1].is_ascii_whitespace() {
+        end -= 1;
+    }
+
+    if end >= start + 2 && bytes[end - 2] == b'm' && bytes[end - 1] == b's' {
+        return Duration::from_millis(__parse_threshold_digits(bytes, start, end - 2));
+    }
+    if end >= start + 2 && bytes[end - 2] == b'u' && bytes[end - 1] == b's' {
+        return Duration::from_micros(__parse_threshold_digits(bytes, start, end - 2));
+    }
+    // "µs": a 2-byte UTF-8 sequence (0xC2 0xB5) followed by ASCII 's', since
+    // `str::strip_suffix` isn't `const fn` and byte comparisons are all
+    // that's available here.
+    if end >= start + 3
+        && bytes[end - 3] == 0xC2
+        && bytes[end - 2] == 0xB5
+        && bytes[end - 1] == b's'
+    {
+        return Duration::from_micros(__parse_threshold_digits(bytes, start, end - 3));
+    }
+    if end >= start + 2 && bytes[end - 2] == b'n' && bytes[end - 1] == b's' {
+        return Duration::from_nanos(__parse_threshold_digits(bytes, start, end - 2));
+    }
+    if end > start && bytes[end - 1] == b's' {
+        return Duration::from_secs(__parse_threshold_digits(bytes, start, end - 1));
+    }
+
+    panic!("hemera: threshold must end with 'ms', 'us', 'ns', or 's'");
+}
+
+/// Maps a `level` literal to whether it should route through stderr, at
+/// compile time, rejecting anything that isn't one of the five levels
+/// `#[measure_time(level = ...)]` accepts. Matching on the literal token
+/// itself (rather than comparing `&str`s at runtime) is what lets an unknown
+/// level fail the build instead of silently falling back to a default.
+///
+/// Takes `$level` as a `tt` rather than re-parsing it as a `literal`: a
+/// fragment already captured as `:literal` by the caller's matcher becomes
+/// opaque and can no longer match a literal pattern in a nested macro, only
+/// another `:literal`/`:tt` binding — so `__hemera_measure_time_block!` below
+/// forwards its `$level` as `tt` to keep the raw token matchable here.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __hemera_level_use_stderr {
+    ("trace") => {
+        true
+    };
+    ("debug") => {
+        true
+    };
+    ("info") => {
+        false
+    };
+    ("warn") => {
+        true
+    };
+    ("error") => {
+        true
+    };
+    ($other:literal) => {
+        compile_error!(concat!(
+            "hemera: level must be one of \"trace\", \"debug\", \"info\", \"warn\", \"error\", got ",
+            stringify!($other),
+        ))
+    };
+}
+
+/// Times a block and returns its value. See the [module docs](self) for usage.
+#[macro_export]
+macro_rules! __hemera_measure_time_block {
+    (
+        $(name = $name:literal,)?
+        $(level = $level:tt,)?
+        $(threshold = $threshold:literal,)?
+        $body:block
+    ) => {{
+        #[allow(unused_mut, unused_assignments)]
+        let mut __hemera_name: &'static str = "block";
+        $( __hemera_name = $name; )?
+
+        #[allow(unused_mut, unused_assignments)]
+        let mut __hemera_use_debug = false;
+        $( __hemera_use_debug = $crate::__hemera_level_use_stderr!($level); )?
+
+        #[allow(unused_mut, unused_assignments)]
+        let mut __hemera_threshold: ::std::option::Option<::std::time::Duration> = None;
+        $(
+            // Bound through a `const` rather than called inline: a malformed
+            // `$threshold` then panics during `const` evaluation, which rustc
+            // reports as a compile error instead of a runtime panic on first
+            // call. See `__parse_threshold`'s doc comment.
+            const __HEMERA_THRESHOLD: ::std::time::Duration =
+                $crate::block::__parse_threshold($threshold);
+            __hemera_threshold = Some(__HEMERA_THRESHOLD);
+        )?
+
+        let __hemera_start = ::std::time::Instant::now();
+        let __hemera_result = $body;
+        let __hemera_elapsed = __hemera_start.elapsed();
+
+        if __hemera_threshold.map_or(true, |threshold| __hemera_elapsed >= threshold) {
+            $crate::__private::dispatch(__hemera_name, __hemera_elapsed, __hemera_use_debug);
+        }
+
+        __hemera_result
+    }};
+}
+
+pub use __hemera_measure_time_block as measure_time;
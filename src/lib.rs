@@ -5,9 +5,9 @@
 //! ## Usage
 //!
 //! ```rust
-//! use hemera::hemera;
+//! use hemera::measure_time;
 //!
-//! #[hemera]
+//! #[measure_time]
 //! fn calculate_fibonacci(n: u32) -> u32 {
 //!     if n <= 1 {
 //!         n
@@ -16,12 +16,12 @@
 //!     }
 //! }
 //!
-//! #[hemera(name = "CustomTimer", level = "debug")]
+//! #[measure_time(name = "CustomTimer", level = "debug")]
 //! fn slow_function() {
 //!     std::thread::sleep(std::time::Duration::from_millis(100));
 //! }
 //!
-//! #[hemera(threshold = "50ms")]
+//! #[measure_time(threshold = "50ms")]
 //! fn maybe_slow(n: u32) {
 //!     // Only logs if execution takes more than 50ms
 //!     std::thread::sleep(std::time::Duration::from_millis(n as u64));
@@ -31,9 +31,9 @@
 //! ## Async Support
 //!
 //! ```rust
-//! use hemera::hemera;
+//! use hemera::measure_time;
 //!
-//! #[hemera]
+//! #[measure_time]
 //! async fn fetch_data() -> String {
 //!     // Async function timing
 //!     "data".to_string()
@@ -48,251 +48,114 @@
 //! [dependencies]
 //! hemera = { version = "0.1", features = ["tracing"] }
 //! ```
+//!
+//! ## Aggregated Stats
+//!
+//! Enable the `stats` feature and pass `aggregate = true` to fold every call
+//! into a process-global histogram instead of printing it:
+//!
+//! ```toml
+//! [dependencies]
+//! hemera = { version = "0.1", features = ["stats"] }
+//! ```
+//!
+//! ```rust,ignore
+//! use hemera::measure_time;
+//!
+//! #[measure_time(aggregate = true)]
+//! fn hot_path() {
+//!     // called thousands of times; printing each call would be noise
+//! }
+//!
+//! fn main() {
+//!     hot_path();
+//!     println!("{}", hemera::report());
+//! }
+//! ```
+//!
+//! ## Custom Sinks
+//!
+//! By default samples print to stdout/stderr. Call [`set_sink`] once at
+//! startup to route them somewhere else instead (see [`Sink`]):
+//!
+//! ```rust,ignore
+//! use hemera::{measure_time, Sink};
+//! use std::time::Duration;
+//!
+//! struct CountingSink;
+//! impl Sink for CountingSink {
+//!     fn record(&self, name: &str, elapsed: Duration) {
+//!         println!("{name} took {elapsed:?}");
+//!     }
+//! }
+//!
+//! hemera::set_sink(CountingSink);
+//! ```
+//!
+//! ## Timing a Block
+//!
+//! `#[measure_time]` only wraps whole functions. To time an arbitrary
+//! expression in place, use `hemera::block::measure_time!` instead:
+//!
+//! ```rust
+//! use hemera::block::measure_time;
+//!
+//! let rows = measure_time!(name = "db_query", { 1 + 1 });
+//! ```
+//!
+//! ## Repeated Sampling
+//!
+//! For micro-profiling a hot call site, `samples = N` keeps the last `N`
+//! durations in a ring buffer and logs median/MAD/min over the window once
+//! it fills, instead of logging (or aggregating) every single call:
+//!
+//! ```rust
+//! use hemera::measure_time;
+//!
+//! #[measure_time(samples = 100)]
+//! fn hot_path() {
+//!     // logs one summary line every 100 calls
+//! }
+//! ```
 
-use proc_macro::TokenStream;
-use quote::quote;
-use syn::{
-    parse::{Parse, ParseStream},
-    parse_macro_input,
-    punctuated::Punctuated,
-    Expr, ItemFn, Lit, Meta, MetaNameValue, Token,
-};
-
-/// Configuration for the hemera macro
-struct HemeraConfig {
-    name: Option<String>,
-    level: Option<String>,
-    threshold: Option<String>,
-}
-
-impl Parse for HemeraConfig {
-    fn parse(input: ParseStream) -> syn::Result<Self> {
-        let mut config = HemeraConfig {
-            name: None,
-            level: None,
-            threshold: None,
-        };
-
-        let metas = Punctuated::<Meta, Token![,]>::parse_terminated(input)?;
-
-        for meta in metas {
-            match meta {
-                Meta::NameValue(MetaNameValue { path, value, .. }) => {
-                    let ident = path
-                        .get_ident()
-                        .ok_or_else(|| syn::Error::new_spanned(&path, "Expected identifier"))?;
+pub use hemera_macros::measure_time;
 
-                    match ident.to_string().as_str() {
-                        "name" => {
-                            if let Expr::Lit(expr_lit) = value {
-                                if let Lit::Str(lit_str) = &expr_lit.lit {
-                                    config.name = Some(lit_str.value());
-                                }
-                            }
-                        }
-                        "level" => {
-                            if let Expr::Lit(expr_lit) = value {
-                                if let Lit::Str(lit_str) = &expr_lit.lit {
-                                    let level = lit_str.value();
-                                    if level != "debug" && level != "info" {
-                                        return Err(syn::Error::new_spanned(
-                                            lit_str,
-                                            "level must be either \"debug\" or \"info\"",
-                                        ));
-                                    }
-                                    config.level = Some(level);
-                                }
-                            }
-                        }
-                        "threshold" => {
-                            if let Expr::Lit(expr_lit) = value {
-                                if let Lit::Str(lit_str) = &expr_lit.lit {
-                                    config.threshold = Some(lit_str.value());
-                                }
-                            }
-                        }
-                        _ => {
-                            return Err(syn::Error::new_spanned(
-                                ident,
-                                format!("Unknown attribute: {}", ident),
-                            ));
-                        }
-                    }
-                }
-                _ => {
-                    return Err(syn::Error::new_spanned(meta, "Expected name-value pair"));
-                }
-            }
-        }
+pub mod block;
 
-        Ok(config)
-    }
-}
+mod sink;
 
-/// Parse threshold string like "10ms", "1s", "500us" into Duration expression
-fn parse_threshold(threshold_str: &str) -> syn::Result<proc_macro2::TokenStream> {
-    let threshold_str = threshold_str.trim();
+pub use sink::{set_sink, Sink};
 
-    let (value_str, unit) = if let Some(stripped) = threshold_str.strip_suffix("ms") {
-        (stripped, "millis")
-    } else if let Some(stripped) = threshold_str
-        .strip_suffix("us")
-        .or_else(|| threshold_str.strip_suffix("µs"))
-    {
-        (stripped, "micros")
-    } else if let Some(stripped) = threshold_str.strip_suffix("ns") {
-        (stripped, "nanos")
-    } else if let Some(stripped) = threshold_str.strip_suffix('s') {
-        (stripped, "secs")
-    } else {
-        return Err(syn::Error::new(
-            proc_macro2::Span::call_site(),
-            "Threshold must end with 'ms', 'us', 'ns', or 's'",
-        ));
-    };
+#[cfg(feature = "influx")]
+pub use sink::InfluxSink;
 
-    let value: u64 = value_str.parse().map_err(|_| {
-        syn::Error::new(
-            proc_macro2::Span::call_site(),
-            format!("Invalid threshold value: {}", value_str),
-        )
-    })?;
+#[cfg(feature = "stats")]
+mod stats;
 
-    let duration = match unit {
-        "secs" => quote! { std::time::Duration::from_secs(#value) },
-        "millis" => quote! { std::time::Duration::from_millis(#value) },
-        "micros" => quote! { std::time::Duration::from_micros(#value) },
-        "nanos" => quote! { std::time::Duration::from_nanos(#value) },
-        _ => unreachable!(),
-    };
+#[cfg(feature = "stats")]
+pub use stats::{report, report_on_exit, ReportGuard};
 
-    Ok(duration)
-}
+mod sampling;
 
-/// Attribute macro for measuring function execution time
-///
-/// # Arguments
-///
-/// * `name` - Custom name for the function in logs (default: function name)
-/// * `level` - Log level: "debug" (uses eprintln!) or "info" (uses println!) (default: "info")
-/// * `threshold` - Minimum duration to log (e.g., "10ms", "1s") (default: always log)
-///
-/// # Examples
-///
-/// ```rust
-/// use hemera::hemera;
+/// Items used by code generated by the `measure_time` macro.
 ///
-/// #[hemera]
-/// fn example() {
-///     // Function body
-/// }
-///
-/// #[hemera(name = "MyFunction", level = "debug", threshold = "10ms")]
-/// fn example_with_options() {
-///     // Function body
-/// }
-///
-/// #[hemera]
-/// async fn async_example() {
-///     // Async function body
-/// }
-/// ```
-#[proc_macro_attribute]
-pub fn hemera(attr: TokenStream, item: TokenStream) -> TokenStream {
-    let input = parse_macro_input!(item as ItemFn);
-
-    let config = if attr.is_empty() {
-        HemeraConfig {
-            name: None,
-            level: None,
-            threshold: None,
-        }
-    } else {
-        match syn::parse::<HemeraConfig>(attr) {
-            Ok(config) => config,
-            Err(e) => return e.to_compile_error().into(),
-        }
-    };
-
-    let ItemFn {
-        attrs,
-        vis,
-        sig,
-        block,
-    } = input;
-
-    let fn_name = &sig.ident;
-    let display_name = config.name.unwrap_or_else(|| fn_name.to_string());
-
-    let use_debug = matches!(config.level.as_deref(), Some("debug"));
-
-    let threshold_check = if let Some(threshold_str) = config.threshold {
-        match parse_threshold(&threshold_str) {
-            Ok(duration) => quote! {
-                if __hemera_elapsed >= #duration
-            },
-            Err(e) => return e.to_compile_error().into(),
-        }
-    } else {
-        quote! { if true }
-    };
-
-    let is_async = sig.asyncness.is_some();
-
-    let print_stmt = if use_debug {
-        quote! {
-            eprintln!("⏱ Function `{}` executed in {:.3?}", #display_name, __hemera_elapsed);
-        }
-    } else {
-        quote! {
-            println!("⏱ Function `{}` executed in {:.3?}", #display_name, __hemera_elapsed);
-        }
-    };
-
-    let timing_code = if is_async {
-        quote! {
-            let __hemera_start = std::time::Instant::now();
-            let __hemera_result = async move { #block }.await;
-            let __hemera_elapsed = __hemera_start.elapsed();
-            #threshold_check {
-                #print_stmt
-            }
-            __hemera_result
-        }
-    } else {
-        quote! {
-            let __hemera_start = std::time::Instant::now();
-            let __hemera_result = (|| #block)();
-            let __hemera_elapsed = __hemera_start.elapsed();
-            #threshold_check {
-                #print_stmt
-            }
-            __hemera_result
-        }
-    };
-
-    let tracing_wrapper = if cfg!(feature = "tracing") {
-        quote! {
-            let __hemera_span = tracing::info_span!("hemera", function = #display_name);
-            let __hemera_enter = __hemera_span.enter();
-        }
-    } else {
-        quote! {}
-    };
-
-    let new_block = quote! {
-        {
-            #tracing_wrapper
-            #timing_code
-        }
-    };
-
-    let output = quote! {
-        #(#attrs)*
-        #vis #sig {
-            #new_block
-        }
-    };
-
-    output.into()
+/// Not part of the public API: the macro expands to references into this
+/// module, which is only reachable because callers already depend on
+/// `hemera` to import the macro in the first place. Nothing here is
+/// covered by semver.
+#[doc(hidden)]
+pub mod __private {
+    pub use crate::sink::dispatch;
+
+    pub use crate::sampling::record_sample;
+
+    #[cfg(feature = "stats")]
+    pub use crate::stats::record as record_aggregate;
+
+    // Re-exported so generated code can write `hemera::__private::tracing::event!`
+    // rather than a bare `tracing::event!`, which would instead resolve against
+    // whatever crate named `tracing` (if any) is in scope at the macro's call
+    // site -- i.e. the user's own crate, not this one's dependency.
+    #[cfg(feature = "tracing")]
+    pub use tracing;
 }
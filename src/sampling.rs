@@ -0,0 +1,152 @@
+//! Ring-buffer sampling mode for `#[measure_time(samples = N)]`.
+//!
+//! Rather than logging every call (or folding it into a histogram like
+//! `aggregate` does), this mode keeps the last `N` durations for a function
+//! in a fixed-size ring buffer, keyed by display name like the aggregation
+//! registry. Once the window fills, it computes median, median absolute
+//! deviation (MAD), and min over it and logs one summary line per `N` calls
+//! — numbers that resist the warmup/outlier noise a single reading suffers
+//! from, without pulling in a full benchmarking harness.
+
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+struct Window {
+    samples: Vec<u64>,
+    next: usize,
+    calls: u64,
+}
+
+impl Window {
+    fn new(capacity: usize) -> Self {
+        Self {
+            samples: Vec::with_capacity(capacity),
+            next: 0,
+            calls: 0,
+        }
+    }
+
+    /// Pushes one sample (in nanoseconds), returning a snapshot of the
+    /// window once every `capacity` calls, once it's full.
+    fn push(&mut self, capacity: usize, nanos: u64) -> Option<Vec<u64>> {
+        self.calls += 1;
+
+        if self.samples.len() < capacity {
+            self.samples.push(nanos);
+        } else {
+            self.samples[self.next] = nanos;
+        }
+        self.next = (self.next + 1) % capacity;
+
+        let full = self.samples.len() == capacity;
+        if full && self.calls.is_multiple_of(capacity as u64) {
+            Some(self.samples.clone())
+        } else {
+            None
+        }
+    }
+}
+
+type Registry = HashMap<&'static str, Window>;
+
+static REGISTRY: OnceLock<Mutex<Registry>> = OnceLock::new();
+
+fn registry() -> &'static Mutex<Registry> {
+    REGISTRY.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn median(sorted: &[u64]) -> f64 {
+    let mid = sorted.len() / 2;
+    if sorted.len().is_multiple_of(2) {
+        (sorted[mid - 1] + sorted[mid]) as f64 / 2.0
+    } else {
+        sorted[mid] as f64
+    }
+}
+
+fn median_of_f64(sorted: &[f64]) -> f64 {
+    let mid = sorted.len() / 2;
+    if sorted.len().is_multiple_of(2) {
+        (sorted[mid - 1] + sorted[mid]) / 2.0
+    } else {
+        sorted[mid]
+    }
+}
+
+/// Median, median absolute deviation, and min over a window of nanosecond
+/// samples.
+fn summarize(samples: &mut [u64]) -> (f64, f64, u64) {
+    samples.sort_unstable();
+    let median_ns = median(samples);
+
+    let mut deviations: Vec<f64> = samples
+        .iter()
+        .map(|&sample| (sample as f64 - median_ns).abs())
+        .collect();
+    deviations.sort_by(|a, b| a.total_cmp(b));
+    let mad_ns = median_of_f64(&deviations);
+
+    (median_ns, mad_ns, samples[0])
+}
+
+/// Records one call's elapsed duration for `name`'s sampling window,
+/// creating it on first use, and logs a summary once every `capacity` calls.
+/// `threshold`, when set, suppresses the summary unless the window's median
+/// meets or exceeds it. `use_stderr` picks stdout vs. stderr for the summary
+/// when no `Sink` is installed, matching `level`'s usual meaning on the
+/// non-aggregate path. Called from code generated by
+/// `#[measure_time(samples = N)]`.
+pub fn record_sample(
+    name: &'static str,
+    capacity: usize,
+    elapsed: Duration,
+    threshold: Option<Duration>,
+    use_stderr: bool,
+) {
+    let nanos = elapsed.as_nanos().min(u128::from(u64::MAX)) as u64;
+
+    let snapshot = {
+        let mut registry = registry()
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let window = registry
+            .entry(name)
+            .or_insert_with(|| Window::new(capacity));
+        window.push(capacity, nanos)
+    };
+
+    let Some(mut samples) = snapshot else {
+        return;
+    };
+    let (median_ns, mad_ns, min_ns) = summarize(&mut samples);
+
+    if let Some(threshold) = threshold {
+        if median_ns < threshold.as_nanos() as f64 {
+            return;
+        }
+    }
+
+    let median = Duration::from_nanos(median_ns as u64);
+    let mad = Duration::from_nanos(mad_ns as u64);
+    let min = Duration::from_nanos(min_ns);
+
+    let fallback = format!(
+        "⏱ Function `{}` over last {} calls: median={:.3?} mad={:.3?} min={:.3?}",
+        name,
+        samples.len(),
+        median,
+        mad,
+        min,
+    );
+
+    // Sink::record only carries a single (name, elapsed) pair, so each
+    // summary statistic is routed as its own sub-sample rather than losing
+    // the data by printing it directly and bypassing any installed Sink.
+    crate::sink::dispatch_many(
+        name,
+        &[("median", median), ("mad", mad), ("min", min)],
+        &fallback,
+        use_stderr,
+    );
+}
@@ -0,0 +1,76 @@
+//! Pluggable destinations for timing samples.
+//!
+//! By default `#[measure_time]` prints to stdout/stderr. Installing a [`Sink`]
+//! with [`set_sink`] reroutes every non-aggregated sample to it instead, so
+//! the crate can feed a real metrics pipeline rather than just a debug log.
+//! With the `tracing` feature, `#[measure_time]` emits a tracing event
+//! instead and does not pass its samples to the sink.
+
+use std::sync::OnceLock;
+use std::time::Duration;
+
+#[cfg(feature = "influx")]
+mod influx;
+
+#[cfg(feature = "influx")]
+pub use influx::InfluxSink;
+
+/// Receives one timing sample per instrumented call, except in
+/// `samples = N` mode, which sends one `<name>.median`, `<name>.mad` and
+/// `<name>.min` sample per window summary instead.
+///
+/// Implementations must be cheap and non-blocking: `record` runs inline in
+/// the instrumented function's call path, so anything expensive (formatting,
+/// I/O, network calls) should be handed off to a background thread instead
+/// of done here. See [`InfluxSink`] for the pattern.
+pub trait Sink: Send + Sync {
+    fn record(&self, name: &str, elapsed: Duration);
+}
+
+static SINK: OnceLock<Box<dyn Sink>> = OnceLock::new();
+
+/// Installs the process-global sink. Has no effect if a sink is already
+/// installed (first call wins), matching the set-once semantics of
+/// `std::sync::OnceLock`.
+pub fn set_sink(sink: impl Sink + 'static) {
+    let _ = SINK.set(Box::new(sink));
+}
+
+fn sink() -> Option<&'static dyn Sink> {
+    SINK.get().map(|boxed| boxed.as_ref())
+}
+
+/// Routes one sample to the installed sink, falling back to printing it
+/// when no sink has been set. Called from code generated by `#[measure_time]`.
+pub fn dispatch(name: &'static str, elapsed: Duration, use_debug: bool) {
+    if let Some(sink) = sink() {
+        sink.record(name, elapsed);
+    } else if use_debug {
+        eprintln!("⏱ Function `{}` executed in {:.3?}", name, elapsed);
+    } else {
+        println!("⏱ Function `{}` executed in {:.3?}", name, elapsed);
+    }
+}
+
+/// Routes a batch of named sub-measurements (e.g. a window's median/MAD/min)
+/// to the installed sink, one `Sink::record` call per entry keyed
+/// `"<name>.<suffix>"`, falling back to printing `fallback` verbatim when no
+/// sink has been set. `use_stderr` picks stdout vs. stderr for that fallback,
+/// matching `dispatch`'s `level` semantics. Used for summaries that don't fit
+/// `Sink::record`'s single-`(name, elapsed)` shape.
+pub(crate) fn dispatch_many(
+    name: &str,
+    samples: &[(&str, Duration)],
+    fallback: &str,
+    use_stderr: bool,
+) {
+    if let Some(sink) = sink() {
+        for (suffix, elapsed) in samples {
+            sink.record(&format!("{name}.{suffix}"), *elapsed);
+        }
+    } else if use_stderr {
+        eprintln!("{fallback}");
+    } else {
+        println!("{fallback}");
+    }
+}
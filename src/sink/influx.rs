@@ -0,0 +1,148 @@
+//! Background InfluxDB line-protocol exporter.
+
+use super::Sink;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+struct Sample {
+    name: String,
+    elapsed_ns: u64,
+    timestamp_ns: u128,
+}
+
+/// Escapes a line-protocol tag value: commas and spaces delimit the tag set
+/// and field set, `=` delimits a tag's key from its value, and a literal
+/// newline terminates the line itself, so any of those appearing unescaped
+/// in `#[measure_time(name = "...")]` (or a `dispatch_many` suffix like
+/// `name.median`) would truncate or corrupt the line(s) InfluxDB receives.
+fn escape_tag_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace(',', "\\,")
+        .replace(' ', "\\ ")
+        .replace('=', "\\=")
+        .replace('\n', "\\n")
+}
+
+/// Batches samples onto a bounded channel and ships them to an InfluxDB
+/// HTTP write endpoint from a dedicated background thread.
+///
+/// The channel is bounded and sends are non-blocking (`try_send`): once it's
+/// full, new samples are dropped rather than stalling the measured code.
+pub struct InfluxSink {
+    tx: crossbeam_channel::Sender<Sample>,
+}
+
+impl InfluxSink {
+    /// Spawns the background exporter thread and returns a sink that feeds it.
+    ///
+    /// `endpoint` should be a full InfluxDB write URL, e.g.
+    /// `http://localhost:8086/write?db=hemera`. Samples are batched up to
+    /// `batch_size` at a time, or flushed after `flush_interval` elapses,
+    /// whichever comes first.
+    pub fn new(endpoint: impl Into<String>, batch_size: usize, flush_interval: Duration) -> Self {
+        let (tx, rx) = crossbeam_channel::bounded(4096);
+        let endpoint = endpoint.into();
+
+        std::thread::Builder::new()
+            .name("hemera-influx-exporter".to_string())
+            .spawn(move || Self::run(rx, endpoint, batch_size, flush_interval))
+            .expect("failed to spawn hemera influx exporter thread");
+
+        Self { tx }
+    }
+
+    fn run(
+        rx: crossbeam_channel::Receiver<Sample>,
+        endpoint: String,
+        batch_size: usize,
+        flush_interval: Duration,
+    ) {
+        let mut batch = Vec::with_capacity(batch_size);
+        let mut deadline = Instant::now() + flush_interval;
+
+        loop {
+            match rx.recv_deadline(deadline) {
+                Ok(sample) => {
+                    batch.push(sample);
+                    if batch.len() >= batch_size {
+                        Self::flush(&endpoint, &mut batch);
+                        deadline = Instant::now() + flush_interval;
+                    }
+                }
+                Err(crossbeam_channel::RecvTimeoutError::Timeout) => {
+                    if !batch.is_empty() {
+                        Self::flush(&endpoint, &mut batch);
+                    }
+                    deadline = Instant::now() + flush_interval;
+                }
+                Err(crossbeam_channel::RecvTimeoutError::Disconnected) => {
+                    if !batch.is_empty() {
+                        Self::flush(&endpoint, &mut batch);
+                    }
+                    return;
+                }
+            }
+        }
+    }
+
+    fn flush(endpoint: &str, batch: &mut Vec<Sample>) {
+        let body = batch
+            .iter()
+            .map(|sample| {
+                format!(
+                    "hemera,function={} elapsed_ns={}i {}",
+                    escape_tag_value(&sample.name),
+                    sample.elapsed_ns,
+                    sample.timestamp_ns
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        if let Err(err) = ureq::post(endpoint).send_string(&body) {
+            eprintln!("hemera: failed to export samples to influxdb: {err}");
+        }
+
+        batch.clear();
+    }
+}
+
+impl Sink for InfluxSink {
+    fn record(&self, name: &str, elapsed: Duration) {
+        let timestamp_ns = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos();
+
+        let sample = Sample {
+            name: name.to_string(),
+            elapsed_ns: elapsed.as_nanos().min(u128::from(u64::MAX)) as u64,
+            timestamp_ns,
+        };
+
+        let _ = self.tx.try_send(sample);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::escape_tag_value;
+
+    #[test]
+    fn escapes_every_line_protocol_special_character() {
+        assert_eq!(escape_tag_value("plain"), "plain");
+        assert_eq!(escape_tag_value("a,b"), "a\\,b");
+        assert_eq!(escape_tag_value("a b"), "a\\ b");
+        assert_eq!(escape_tag_value("a=b"), "a\\=b");
+        assert_eq!(escape_tag_value("a\\b"), "a\\\\b");
+        assert_eq!(escape_tag_value("a\nb"), "a\\nb");
+    }
+
+    #[test]
+    fn escapes_a_name_combining_every_special_character() {
+        assert_eq!(
+            escape_tag_value("my,func=a b\\c\nd"),
+            "my\\,func\\=a\\ b\\\\c\\nd"
+        );
+    }
+}
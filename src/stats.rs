@@ -0,0 +1,105 @@
+//! Process-global aggregation for calls made with `#[measure_time(aggregate = true)]`.
+//!
+//! Samples are folded into a per-function HDR histogram rather than printed
+//! one by one, so hot functions called thousands of times stay cheap to
+//! instrument: recording is a single mutex lock plus a histogram update, and
+//! memory stays bounded regardless of call count.
+
+use hdrhistogram::Histogram;
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+/// Lower/upper bounds (in nanoseconds) and significant-digit precision used
+/// for every per-function histogram: 1ns to 60s at 3 significant digits.
+const HISTOGRAM_LOW: u64 = 1;
+const HISTOGRAM_HIGH: u64 = 60_000_000_000;
+const HISTOGRAM_SIGFIGS: u8 = 3;
+
+type Registry = HashMap<&'static str, Histogram<u64>>;
+
+static REGISTRY: OnceLock<Mutex<Registry>> = OnceLock::new();
+
+fn registry() -> &'static Mutex<Registry> {
+    REGISTRY.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Records one call's elapsed duration into `name`'s histogram, creating it
+/// on first use. Called from code generated by `#[measure_time(aggregate = true)]`.
+pub fn record(name: &'static str, elapsed: Duration) {
+    let nanos = elapsed.as_nanos().min(u128::from(u64::MAX)) as u64;
+    let mut registry = registry()
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    let histogram = registry.entry(name).or_insert_with(|| {
+        Histogram::new_with_bounds(HISTOGRAM_LOW, HISTOGRAM_HIGH, HISTOGRAM_SIGFIGS)
+            .expect("hemera's histogram bounds are always valid")
+    });
+    let _ = histogram.record(nanos);
+}
+
+fn format_nanos(nanos: f64) -> String {
+    format!("{:.3?}", Duration::from_nanos(nanos as u64))
+}
+
+/// Formats a summary table (call count, mean, p50, p90, p99, max) for every
+/// function recorded so far, one row per function, sorted by name.
+///
+/// Returns an empty-table message if nothing has been recorded yet.
+pub fn report() -> String {
+    let registry = registry()
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+    if registry.is_empty() {
+        return "hemera: no aggregated samples recorded".to_string();
+    }
+
+    let mut rows: Vec<_> = registry.iter().collect();
+    rows.sort_by_key(|(name, _)| *name);
+
+    let mut out = String::new();
+    out.push_str(&format!(
+        "{:<30} {:>10} {:>10} {:>10} {:>10} {:>10} {:>10}\n",
+        "function", "n", "mean", "p50", "p90", "p99", "max"
+    ));
+    for (name, histogram) in rows {
+        out.push_str(&format!(
+            "{:<30} {:>10} {:>10} {:>10} {:>10} {:>10} {:>10}\n",
+            name,
+            histogram.len(),
+            format_nanos(histogram.mean()),
+            format_nanos(histogram.value_at_quantile(0.50) as f64),
+            format_nanos(histogram.value_at_quantile(0.90) as f64),
+            format_nanos(histogram.value_at_quantile(0.99) as f64),
+            format_nanos(histogram.max() as f64),
+        ));
+    }
+    out.pop();
+    out
+}
+
+/// Drop guard that prints [`report`] once, when it goes out of scope.
+///
+/// Hold one for the lifetime of `main` (via [`report_on_exit`]) to get the
+/// aggregated summary automatically at process end, without having to call
+/// `report()` on every possible early return.
+pub struct ReportGuard(());
+
+impl Drop for ReportGuard {
+    fn drop(&mut self) {
+        println!("{}", report());
+    }
+}
+
+/// Returns a guard that prints the aggregated report when dropped.
+///
+/// ```rust,ignore
+/// fn main() {
+///     let _hemera_report = hemera::report_on_exit();
+///     // ... run instrumented code ...
+/// } // report is printed here, as main returns
+/// ```
+pub fn report_on_exit() -> ReportGuard {
+    ReportGuard(())
+}
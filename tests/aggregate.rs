@@ -0,0 +1,32 @@
+#![cfg(feature = "stats")]
+
+use hemera::measure_time;
+
+#[measure_time(aggregate = true)]
+fn hot_path() -> u32 {
+    42
+}
+
+#[measure_time(aggregate)]
+fn hot_path_shorthand() -> u32 {
+    7
+}
+
+#[measure_time(name = "Aggregated", aggregate = true)]
+fn hot_path_named() -> u32 {
+    0
+}
+
+#[test]
+fn aggregated_calls_still_return_their_value() {
+    assert_eq!(hot_path(), 42);
+    assert_eq!(hot_path_shorthand(), 7);
+    assert_eq!(hot_path_named(), 0);
+}
+
+#[test]
+fn aggregated_calls_show_up_in_the_report() {
+    hot_path();
+    let report = hemera::report();
+    assert!(report.contains("hot_path"));
+}
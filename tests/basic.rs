@@ -0,0 +1,49 @@
+use hemera::measure_time;
+
+#[measure_time]
+fn simple_sync() -> i32 {
+    42
+}
+
+#[measure_time]
+async fn simple_async() -> String {
+    "hello".to_string()
+}
+
+#[measure_time(name = "Custom")]
+fn with_name() -> i32 {
+    1
+}
+
+#[measure_time(level = "debug")]
+fn with_level() -> i32 {
+    2
+}
+
+#[measure_time(threshold = "10ms")]
+fn with_threshold() -> i32 {
+    3
+}
+
+#[measure_time(name = "Test", level = "debug", threshold = "5ms")]
+fn all_attrs() -> i32 {
+    4
+}
+
+#[test]
+fn sync_function_returns_its_value() {
+    assert_eq!(simple_sync(), 42);
+}
+
+#[tokio::test]
+async fn async_function_returns_its_value() {
+    assert_eq!(simple_async().await, "hello");
+}
+
+#[test]
+fn attributes_do_not_change_the_return_value() {
+    assert_eq!(with_name(), 1);
+    assert_eq!(with_level(), 2);
+    assert_eq!(with_threshold(), 3);
+    assert_eq!(all_attrs(), 4);
+}
@@ -0,0 +1,51 @@
+use hemera::block::measure_time;
+
+#[test]
+fn times_a_plain_block() {
+    let sum = measure_time!({ 1 + 1 });
+    assert_eq!(sum, 2);
+}
+
+#[test]
+fn times_a_named_block() {
+    let named = measure_time!(name = "named_block", { 40 + 2 });
+    assert_eq!(named, 42);
+}
+
+#[test]
+fn times_a_leveled_block() {
+    let leveled = measure_time!(level = "debug", { "debug" });
+    assert_eq!(leveled, "debug");
+}
+
+#[test]
+fn times_a_thresholded_block() {
+    let thresholded = measure_time!(threshold = "10ms", { 7 });
+    assert_eq!(thresholded, 7);
+}
+
+#[test]
+fn times_a_block_thresholded_in_micros() {
+    // Exercises the "µs" (as opposed to the ASCII "us") suffix, which
+    // `__parse_threshold` matches on raw UTF-8 bytes rather than `char`s.
+    let thresholded = measure_time!(threshold = "500µs", { 13 });
+    assert_eq!(thresholded, 13);
+}
+
+#[test]
+fn times_a_block_with_all_options() {
+    let all = measure_time!(name = "all", level = "debug", threshold = "1ns", { 99 });
+    assert_eq!(all, 99);
+}
+
+#[tokio::test]
+async fn times_a_block_containing_an_await() {
+    // The block is timed in place rather than wrapped in a closure, so a
+    // real `.await` point inside it must suspend the enclosing async fn as
+    // usual instead of, say, failing to compile or blocking the executor.
+    let data = measure_time!(name = "fetch", {
+        tokio::time::sleep(std::time::Duration::from_millis(1)).await;
+        "fetched"
+    });
+    assert_eq!(data, "fetched");
+}
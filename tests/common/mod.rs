@@ -0,0 +1,35 @@
+//! Shared helpers for hemera's integration tests.
+//!
+//! Lives under `tests/common/` rather than `tests/common.rs` so Cargo
+//! doesn't treat it as its own test binary (it has no `#[test]`s of its
+//! own) -- the usual trick for code shared across integration test files.
+
+use hemera::Sink;
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+static RECORDS: OnceLock<Mutex<Vec<(String, Duration)>>> = OnceLock::new();
+
+fn records() -> &'static Mutex<Vec<(String, Duration)>> {
+    RECORDS.get_or_init(|| Mutex::new(Vec::new()))
+}
+
+/// A [`Sink`] that appends every `(name, elapsed)` pair it's given to a
+/// process-global list instead of printing it.
+///
+/// `hemera::set_sink` takes ownership of the sink it's given, so there's no
+/// handle left to read recorded samples back off of it directly; storing
+/// them in a free-standing static instead (read back via [`recorded`]) works
+/// around that for tests.
+pub struct RecordingSink;
+
+impl Sink for RecordingSink {
+    fn record(&self, name: &str, elapsed: Duration) {
+        records().lock().unwrap().push((name.to_string(), elapsed));
+    }
+}
+
+/// Every sample recorded by [`RecordingSink`] so far, in call order.
+pub fn recorded() -> Vec<(String, Duration)> {
+    records().lock().unwrap().clone()
+}
@@ -0,0 +1,32 @@
+use hemera::measure_time;
+
+#[measure_time]
+fn generic_function<T: Clone>(value: T) -> T {
+    value.clone()
+}
+
+#[measure_time]
+async fn generic_async<T: std::fmt::Display>(value: T) -> String {
+    format!("{}", value)
+}
+
+#[measure_time(name = "GenericWithAttrs")]
+fn generic_with_attrs<T, U>(a: T, b: U) -> (T, U) {
+    (a, b)
+}
+
+#[test]
+fn generic_function_preserves_its_value() {
+    assert_eq!(generic_function(7), 7);
+    assert_eq!(generic_function("hi".to_string()), "hi");
+}
+
+#[tokio::test]
+async fn generic_async_formats_its_value() {
+    assert_eq!(generic_async(42).await, "42");
+}
+
+#[test]
+fn generic_with_attrs_preserves_both_values() {
+    assert_eq!(generic_with_attrs(1, "two"), (1, "two"));
+}
@@ -0,0 +1,98 @@
+#![cfg(feature = "influx")]
+
+use hemera::InfluxSink;
+use hemera::Sink;
+use std::io::{BufRead, BufReader, Read};
+use std::net::TcpListener;
+use std::sync::mpsc;
+use std::time::Duration;
+
+/// Accepts HTTP requests on a local listener, one per connection, and hands
+/// each request's body back over `tx`. Stands in for an InfluxDB write endpoint so
+/// `InfluxSink`'s batching and line-protocol framing can be checked without
+/// a real server.
+fn spawn_capturing_endpoint() -> (String, mpsc::Receiver<String>) {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("failed to bind test listener");
+    let addr = listener.local_addr().expect("listener has no local addr");
+    let (tx, rx) = mpsc::channel();
+
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.expect("accept failed");
+            let mut reader = BufReader::new(&mut stream);
+
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).expect("failed to read header");
+                let line = line.trim_end();
+                if line.is_empty() {
+                    break;
+                }
+                if let Some(value) = line.strip_prefix("Content-Length: ") {
+                    content_length = value.parse().unwrap_or(0);
+                }
+            }
+
+            let mut body = vec![0u8; content_length];
+            reader.read_exact(&mut body).expect("failed to read body");
+            let body = String::from_utf8(body).expect("body wasn't utf8");
+
+            drop(reader);
+            stream
+                .write_all_response()
+                .expect("failed to write response");
+
+            if tx.send(body).is_err() {
+                return;
+            }
+        }
+    });
+
+    (format!("http://{addr}/write?db=hemera"), rx)
+}
+
+/// Small helper so the server loop above reads as a plain HTTP exchange.
+trait WriteAllResponse {
+    fn write_all_response(&mut self) -> std::io::Result<()>;
+}
+
+impl WriteAllResponse for std::net::TcpStream {
+    fn write_all_response(&mut self) -> std::io::Result<()> {
+        use std::io::Write;
+        self.write_all(b"HTTP/1.1 204 No Content\r\ncontent-length: 0\r\n\r\n")
+    }
+}
+
+#[test]
+fn flushes_once_the_batch_fills_and_escapes_the_emitted_line() {
+    let (endpoint, bodies) = spawn_capturing_endpoint();
+    let sink = InfluxSink::new(endpoint, 2, Duration::from_secs(60));
+
+    sink.record("first", Duration::from_millis(1));
+    sink.record("a,tag=with spaces", Duration::from_millis(2));
+
+    let body = bodies
+        .recv_timeout(Duration::from_secs(5))
+        .expect("batch never reached the endpoint");
+
+    let lines: Vec<&str> = body.lines().collect();
+    assert_eq!(lines.len(), 2);
+    assert!(lines[0].starts_with("hemera,function=first elapsed_ns=1000000i "));
+    assert!(lines[1].starts_with("hemera,function=a\\,tag\\=with\\ spaces elapsed_ns=2000000i "));
+}
+
+#[test]
+fn flushes_on_the_interval_timeout_even_with_a_partial_batch() {
+    let (endpoint, bodies) = spawn_capturing_endpoint();
+    let sink = InfluxSink::new(endpoint, 100, Duration::from_millis(50));
+
+    sink.record("lonely", Duration::from_millis(3));
+
+    let body = bodies
+        .recv_timeout(Duration::from_secs(5))
+        .expect("partial batch was never flushed on the interval timeout");
+
+    assert_eq!(body.lines().count(), 1);
+    assert!(body.starts_with("hemera,function=lonely elapsed_ns=3000000i "));
+}
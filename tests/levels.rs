@@ -0,0 +1,35 @@
+use hemera::measure_time;
+
+#[measure_time(level = "trace")]
+fn at_trace() -> &'static str {
+    "trace"
+}
+
+#[measure_time(level = "debug")]
+fn at_debug() -> &'static str {
+    "debug"
+}
+
+#[measure_time(level = "info")]
+fn at_info() -> &'static str {
+    "info"
+}
+
+#[measure_time(level = "warn")]
+fn at_warn() -> &'static str {
+    "warn"
+}
+
+#[measure_time(level = "error")]
+fn at_error() -> &'static str {
+    "error"
+}
+
+#[test]
+fn every_level_compiles_and_runs() {
+    assert_eq!(at_trace(), "trace");
+    assert_eq!(at_debug(), "debug");
+    assert_eq!(at_info(), "info");
+    assert_eq!(at_warn(), "warn");
+    assert_eq!(at_error(), "error");
+}
@@ -0,0 +1,88 @@
+mod common;
+
+use common::{recorded, RecordingSink};
+use hemera::__private::record_sample;
+use hemera::measure_time;
+use std::time::Duration;
+
+#[measure_time(samples = 4)]
+fn hot_path() -> u32 {
+    42
+}
+
+#[measure_time(name = "Sampled", samples = 4, threshold = "1us")]
+fn hot_path_with_threshold() -> u32 {
+    7
+}
+
+// `level` combines with `samples` too: it picks stdout vs. stderr for the
+// periodic summary instead of being rejected like `aggregate` + `threshold`
+// is. This only asserts it compiles and still returns the right value; the
+// stdout vs. stderr choice itself is not checked by any test here.
+#[measure_time(level = "debug", samples = 4)]
+fn hot_path_on_stderr() -> u32 {
+    13
+}
+
+#[test]
+fn sampled_calls_still_return_their_value() {
+    // Enough calls to fill the ring buffer a few times over and exercise the
+    // median/MAD/min summary path in `record_sample`.
+    for _ in 0..20 {
+        assert_eq!(hot_path(), 42);
+        assert_eq!(hot_path_with_threshold(), 7);
+        assert_eq!(hot_path_on_stderr(), 13);
+    }
+}
+
+#[test]
+fn sink_receives_accurate_median_mad_and_min() {
+    hemera::set_sink(RecordingSink);
+
+    // Crafted nanosecond durations rather than real sleeps, so the expected
+    // median/MAD/min can be computed by hand instead of tolerating timing
+    // jitter: sorted = [100, 200, 300, 400, 100_000], median = 300 (middle of
+    // 5), deviations from it sorted = [0, 100, 100, 200, 99_700], so
+    // mad = 100, and min = 100.
+    let samples_ns = [100u64, 200, 300, 400, 100_000];
+    for &nanos in &samples_ns {
+        record_sample(
+            "stats_target",
+            samples_ns.len(),
+            Duration::from_nanos(nanos),
+            None,
+            false,
+        );
+    }
+
+    let records = recorded();
+    let get = |suffix: &str| {
+        let key = format!("stats_target.{suffix}");
+        records
+            .iter()
+            .find(|(name, _)| *name == key)
+            .unwrap_or_else(|| panic!("sink never received {key}"))
+            .1
+    };
+
+    assert_eq!(get("median"), Duration::from_nanos(300));
+    assert_eq!(get("mad"), Duration::from_nanos(100));
+    assert_eq!(get("min"), Duration::from_nanos(100));
+}
+
+#[test]
+fn sink_routes_use_stderr_through_to_record_sample() {
+    hemera::set_sink(RecordingSink);
+
+    // A `Sink`, once installed, takes every sample regardless of `level` --
+    // `use_stderr` only picks stdout vs. stderr for the no-sink fallback.
+    // This pins that a `record_sample` call with `use_stderr = true` still
+    // reaches the installed sink rather than being diverted to stderr.
+    record_sample("stderr_target", 1, Duration::from_nanos(1), None, true);
+
+    let records = recorded();
+    assert!(
+        records.iter().any(|(name, _)| name == "stderr_target.min"),
+        "sink never received a sample routed with use_stderr = true"
+    );
+}
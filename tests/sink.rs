@@ -0,0 +1,30 @@
+mod common;
+
+use common::{recorded, RecordingSink};
+use hemera::__private::dispatch;
+use std::time::Duration;
+
+#[test]
+fn sink_routes_dispatch_and_falls_back_without_one() {
+    // No sink has been installed yet in this process: this should take the
+    // println!/eprintln! fallback path in `dispatch` rather than panicking
+    // or silently doing nothing. Must run before `set_sink` below, since
+    // installing a sink is a one-way, process-global switch.
+    dispatch("fallback_target", Duration::from_millis(1), false);
+
+    hemera::set_sink(RecordingSink);
+
+    // Called directly, rather than through `#[measure_time]`, so this test
+    // exercises the `Sink` mechanism itself regardless of whether the
+    // `tracing` feature is enabled -- under that feature the macro routes
+    // through a tracing event instead of `dispatch`, bypassing `Sink`
+    // entirely by design.
+    dispatch("sink_target", Duration::from_millis(5), false);
+
+    let records = recorded();
+    let (_, elapsed) = records
+        .iter()
+        .find(|(name, _)| name == "sink_target")
+        .expect("installed sink never recorded the dispatched call");
+    assert_eq!(*elapsed, Duration::from_millis(5));
+}
@@ -0,0 +1,85 @@
+#![cfg(feature = "tracing")]
+
+mod common;
+
+use common::{recorded, RecordingSink};
+use hemera::measure_time;
+use std::io;
+use std::sync::{Arc, Mutex};
+
+#[measure_time(level = "warn")]
+fn traced_function() -> &'static str {
+    "traced"
+}
+
+/// Writes every log line into a shared buffer instead of stdout, so the test
+/// can inspect what the installed subscriber actually received.
+#[derive(Clone)]
+struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+impl io::Write for SharedBuffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<'a> tracing_subscriber::fmt::MakeWriter<'a> for SharedBuffer {
+    type Writer = SharedBuffer;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        self.clone()
+    }
+}
+
+#[test]
+fn tracing_feature_emits_a_structured_event_instead_of_dispatching() {
+    hemera::set_sink(RecordingSink);
+
+    let buffer = SharedBuffer(Arc::new(Mutex::new(Vec::new())));
+    let subscriber = tracing_subscriber::fmt()
+        .with_writer(buffer.clone())
+        .with_ansi(false)
+        .without_time()
+        .finish();
+
+    let result = tracing::subscriber::with_default(subscriber, traced_function);
+    assert_eq!(result, "traced");
+
+    let logged = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+
+    // The event carries the WARN level from `level = "warn"` on the macro,
+    // the function's display name, and its elapsed time, rather than the
+    // `dispatch` fallback's "⏱" line. The buffer only holds the subscriber's
+    // output, so the installed sink is what shows `dispatch` was never called.
+    assert!(
+        logged.contains("WARN"),
+        "expected a WARN-level event, got:\n{logged}"
+    );
+    assert!(
+        logged.contains("function completed"),
+        "expected the event's message, got:\n{logged}"
+    );
+    assert!(
+        logged.contains("function=\"traced_function\""),
+        "expected the `function` field, got:\n{logged}"
+    );
+    assert!(
+        logged.contains("elapsed_ms="),
+        "expected the `elapsed_ms` field, got:\n{logged}"
+    );
+    assert!(
+        !logged.contains('⏱'),
+        "tracing feature should suppress the dispatch fallback, got:\n{logged}"
+    );
+    assert!(
+        !recorded()
+            .iter()
+            .any(|(name, _)| name == "traced_function"),
+        "tracing feature should not dispatch the sample to the installed sink"
+    );
+}